@@ -0,0 +1,95 @@
+//! Events represent single points in time during the execution of a program.
+
+use ::{Metadata, field};
+use span::{Parent, Span};
+
+/// Attributes provided to a `Subscriber` describing a new event when it
+/// occurs.
+///
+/// Unlike a [`NewSpan`](::span::NewSpan), an `Event` describes something
+/// that happened at a single point in time, rather than a period that is
+/// entered and exited. An `Event` is constructed and immediately dispatched
+/// to the current subscriber; it has no lifecycle of its own.
+#[derive(Debug)]
+pub struct Event<'a> {
+    metadata: &'a Metadata<'a>,
+    values: &'a field::ValueSet<'a>,
+    parent: Parent,
+}
+
+// ===== impl Event =====
+
+impl<'a> Event<'a> {
+    /// Returns a new `Event` as having occurred in the context of the
+    /// current span, with the specified metadata and values.
+    pub fn new(metadata: &'a Metadata<'a>, values: &'a field::ValueSet<'a>) -> Self {
+        Self {
+            metadata,
+            values,
+            parent: Parent::Current,
+        }
+    }
+
+    /// Returns a new `Event` at the root of its own trace tree, with the
+    /// specified metadata and values.
+    pub fn new_root(metadata: &'a Metadata<'a>, values: &'a field::ValueSet<'a>) -> Self {
+        Self {
+            metadata,
+            values,
+            parent: Parent::Root,
+        }
+    }
+
+    /// Returns a new `Event` as having occurred in the context of the
+    /// specified parent span, with the specified metadata and values.
+    pub fn child_of(parent: Span, metadata: &'a Metadata<'a>, values: &'a field::ValueSet<'a>) -> Self {
+        Self {
+            metadata,
+            values,
+            parent: Parent::Explicit(parent),
+        }
+    }
+
+    /// Constructs a new `Event` as a child of the current span, and
+    /// immediately dispatches it to the current subscriber.
+    ///
+    /// This is a convenience over calling [`new`](Event::new) and then
+    /// handing the resulting `Event` to the subscriber returned by
+    /// [`Dispatch::get_default`](::dispatcher::Dispatch::get_default); most
+    /// callers that just want to record an event should prefer this over
+    /// constructing one by hand.
+    pub fn dispatch(metadata: &'a Metadata<'a>, values: &'a field::ValueSet<'a>) {
+        let event = Self::new(metadata, values);
+        ::dispatcher::Dispatch::get_default(|dispatch| dispatch.event(&event));
+    }
+
+    /// Returns a reference to the event's metadata.
+    pub fn metadata(&self) -> &Metadata<'a> {
+        self.metadata
+    }
+
+    /// Returns a reference to a `ValueSet` containing any values the event
+    /// was recorded with.
+    pub fn values(&self) -> &field::ValueSet<'a> {
+        self.values
+    }
+
+    /// Returns true if the event should be a root.
+    pub fn is_root(&self) -> bool {
+        self.parent.is_root()
+    }
+
+    /// Returns true if the event occurred in the context of the current
+    /// span.
+    pub fn is_contextual(&self) -> bool {
+        self.parent.is_current()
+    }
+
+    /// Returns the event's explicitly-specified parent, if there is one.
+    ///
+    /// Otherwise (if the event is a root or occurred in the context of the
+    /// current span), returns `None`.
+    pub fn parent(&self) -> Option<&Span> {
+        self.parent.explicit()
+    }
+}
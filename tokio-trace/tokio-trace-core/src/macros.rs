@@ -0,0 +1,68 @@
+//! Macros for constructing `NewSpan`s with less boilerplate.
+
+/// Builds a [`NewSpan`](::span::NewSpan), dispatches it to the current
+/// subscriber, and evaluates to the [`Span`](::span::Span) ID the
+/// subscriber assigns it.
+///
+/// Without a `parent:` argument, the new span is a child of the current
+/// span. Passing `parent: None` makes it a root; passing `parent:
+/// Some(span)` makes `span` its explicit parent. An optional `target:`
+/// argument overrides the target recorded in the span's metadata, which
+/// otherwise defaults to `module_path!()`. Up to 32 `key = value` pairs
+/// may follow the name and are recorded as the span's fields.
+///
+/// This removes the boilerplate of hand-assembling a `Metadata` and a
+/// `field::ValueSet` and picking one of [`NewSpan::new`],
+/// [`NewSpan::new_root`], or [`NewSpan::child_of`] — `span!` always
+/// expands to a [`NewSpanBuilder`](::span::NewSpanBuilder), which performs
+/// that choice for you.
+///
+/// A `NewSpan` only borrows its metadata and values, so it can't outlive
+/// the block that builds them; that's why `span!` dispatches it to the
+/// current subscriber itself, rather than handing the `NewSpan` back to
+/// the caller.
+///
+/// # Examples
+///
+/// A span rooted in the current span:
+///
+/// ```ignore
+/// let id = span!(Level::INFO, "my_span", answer = 42, tag = "foo");
+/// ```
+///
+/// A span with an explicit parent:
+///
+/// ```ignore
+/// let id = span!(parent: Some(parent_span), Level::INFO, "my_span");
+/// ```
+///
+/// A root span, with an overridden target:
+///
+/// ```ignore
+/// let id = span!(target: "my_crate::subsystem", parent: None, Level::INFO, "my_span");
+/// ```
+#[macro_export]
+macro_rules! span {
+    (target: $target:expr, parent: $parent:expr, $lvl:expr, $name:expr $(, $key:ident = $value:expr)* $(,)*) => {{
+        static META: $crate::Metadata<'static> =
+            $crate::Metadata::new($name, $target, $lvl, file!(), line!(), module_path!());
+        let values = $crate::field::ValueSet::new(&[
+            $((stringify!($key), &$value as &dyn (::core::fmt::Debug))),*
+        ]);
+        $crate::span::NewSpanBuilder::new(Some($parent)).dispatch(&META, &values)
+    }};
+    (parent: $parent:expr, $lvl:expr, $name:expr $(, $key:ident = $value:expr)* $(,)*) => {
+        span!(target: module_path!(), parent: $parent, $lvl, $name $(, $key = $value)*)
+    };
+    (target: $target:expr, $lvl:expr, $name:expr $(, $key:ident = $value:expr)* $(,)*) => {{
+        static META: $crate::Metadata<'static> =
+            $crate::Metadata::new($name, $target, $lvl, file!(), line!(), module_path!());
+        let values = $crate::field::ValueSet::new(&[
+            $((stringify!($key), &$value as &dyn (::core::fmt::Debug))),*
+        ]);
+        $crate::span::NewSpanBuilder::new(None).dispatch(&META, &values)
+    }};
+    ($lvl:expr, $name:expr $(, $key:ident = $value:expr)* $(,)*) => {
+        span!(target: module_path!(), $lvl, $name $(, $key = $value)*)
+    };
+}
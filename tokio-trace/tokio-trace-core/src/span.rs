@@ -1,5 +1,7 @@
 //! Spans represent periods of time in the execution of a program.
 
+use core::num::NonZeroU64;
+
 use ::{Metadata, field};
 
 /// Identifies a span within the context of a process.
@@ -11,8 +13,12 @@ use ::{Metadata, field};
 /// created, through the [`new_id`](::Subscriber::new_span_id) trait
 /// method. See the documentation for that method for more information on span
 /// ID generation.
+///
+/// The internal representation is a `NonZeroU64`, so `0` is reserved as a
+/// niche value: it is never a valid span ID, which makes `Option<Span>` the
+/// same size as a `Span`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Span(u64);
+pub struct Span(NonZeroU64);
 
 /// Attributes provided to a `Subscriber` describing a new span when it is
 /// created.
@@ -23,27 +29,73 @@ pub struct NewSpan<'a> {
     parent: Parent,
 }
 
+/// Describes the parent of a new span or event.
+///
+/// This is shared between [`NewSpan`] and [`Event`](::Event), since both
+/// spans and events are rooted in the same trace tree and are attached to a
+/// parent in the same three ways.
 #[derive(Debug)]
-enum Parent {
-    /// The new span will be a root span.
+pub(crate) enum Parent {
+    /// The new span or event will be a root.
     Root,
-    /// The new span will be rooted in the current span.
+    /// The new span or event will be rooted in the current span.
     Current,
-    /// The new span has an explicitly-specified parent.
+    /// The new span or event has an explicitly-specified parent.
     Explicit(Span),
 }
 
+// ===== impl Parent =====
+
+impl Parent {
+    pub(crate) fn is_root(&self) -> bool {
+        match self {
+            Parent::Root => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn is_current(&self) -> bool {
+        match self {
+            Parent::Current => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn explicit(&self) -> Option<&Span> {
+        match self {
+            Parent::Explicit(ref p) => Some(p),
+            _ => None,
+        }
+    }
+}
+
 // ===== impl Span =====
 
 impl Span {
     /// Constructs a new span ID from the given `u64`.
+    ///
+    /// `0` is reserved as a niche value: since `Subscriber`s are expected to
+    /// generate span IDs starting at 1, a `u64` of `0` is mapped to `1`
+    /// rather than being treated as an error. Callers that need to reject
+    /// `0` explicitly should use [`try_from_u64`](Span::try_from_u64)
+    /// instead.
     pub fn from_u64(u: u64) -> Self {
-        Span(u)
+        Span(NonZeroU64::new(u).unwrap_or_else(|| NonZeroU64::new(1).unwrap()))
+    }
+
+    /// Constructs a new span ID from the given `u64`, returning `None` if
+    /// `u` is `0`.
+    ///
+    /// Unlike [`from_u64`](Span::from_u64), this does not silently remap
+    /// `0` to another value, so it should be preferred whenever the caller
+    /// wants to catch an invalid ID rather than have one chosen for it.
+    pub fn try_from_u64(u: u64) -> Option<Self> {
+        NonZeroU64::new(u).map(Span)
     }
 
     /// Returns the span's ID as a  `u64`.
     pub fn into_u64(&self) -> u64 {
-        self.0
+        self.0.get()
     }
 }
 
@@ -79,6 +131,22 @@ impl<'a> NewSpan<'a> {
         }
     }
 
+    /// Returns a new `NewSpan` rooted under the span identified by an
+    /// incoming remote [`TraceContext`](::trace_context::TraceContext),
+    /// with the specified metadata and values.
+    ///
+    /// This is the remote-tracing counterpart to
+    /// [`child_of`](NewSpan::child_of): where `child_of` roots a new span
+    /// under a `Span` created in this process, `child_of_context` roots it
+    /// under one described by a context that arrived over an RPC boundary.
+    pub fn child_of_context(
+        context: &::trace_context::TraceContext,
+        metadata: &'a Metadata<'a>,
+        values: &'a field::ValueSet<'a>,
+    ) -> Self {
+        Self::child_of(context.span_id(), metadata, values)
+    }
+
     /// Returns a reference to the new span's metadata.
     pub fn metadata(&self) -> &Metadata<'a> {
         self.metadata
@@ -92,28 +160,71 @@ impl<'a> NewSpan<'a> {
 
     /// Returns true if the new span shoold be a root.
     pub fn is_root(&self) -> bool {
-        match self.parent {
-            Parent::Root => true,
-            _ => false,
-        }
+        self.parent.is_root()
     }
 
     /// Returns true if the new span should be a child of the current span.
     pub fn is_in_current(&self) -> bool {
-        match self.parent {
-            Parent::Current => true,
-            _ => false,
-        }
+        self.parent.is_current()
     }
 
     /// Returns the new span's explicitly-specified parent, if there is one.
     ///
     /// Otherwise (if the new span is a root or is a child of the current span),
-    /// returns false.
+    /// returns `None`.
     pub fn parent(&self) -> Option<&Span> {
+        self.parent.explicit()
+    }
+}
+
+// ===== impl NewSpanBuilder =====
+
+/// Chooses the right `NewSpan` constructor for an optional parent override.
+///
+/// This is the runtime counterpart to the compile-time dispatch the
+/// [`span!`](::span!) macro performs over its `parent:` argument: the
+/// macro always builds a `NewSpanBuilder` and calls
+/// [`dispatch`](NewSpanBuilder::dispatch) on it, and `dispatch` decides
+/// whether that means a `child_of`, a `new_root`, or a `new` so the macro
+/// itself doesn't have to match on the parent shape.
+///
+/// `NewSpanBuilder` is not meant to be constructed directly; use the
+/// `span!` macro instead.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct NewSpanBuilder {
+    parent: Option<Option<Span>>,
+}
+
+impl NewSpanBuilder {
+    /// Returns a new builder. `parent` is `None` for a contextual parent
+    /// (the default), `Some(None)` for an explicit root, and
+    /// `Some(Some(span))` for an explicit parent.
+    #[doc(hidden)]
+    pub fn new(parent: Option<Option<Span>>) -> Self {
+        Self { parent }
+    }
+
+    /// Builds the `NewSpan`, choosing `child_of`, `new_root`, or `new`
+    /// based on the parent given to [`new`](NewSpanBuilder::new).
+    fn finish<'a>(self, metadata: &'a Metadata<'a>, values: &'a field::ValueSet<'a>) -> NewSpan<'a> {
         match self.parent {
-            Parent::Explicit(ref p) => Some(p),
-            _ => None,
+            Some(Some(parent)) => NewSpan::child_of(parent, metadata, values),
+            Some(None) => NewSpan::new_root(metadata, values),
+            None => NewSpan::new(metadata, values),
         }
     }
+
+    /// Builds the `NewSpan` and immediately hands it to the current
+    /// subscriber, returning the `Span` ID the subscriber assigns it.
+    ///
+    /// A `NewSpan` borrows its metadata and values, so it can't outlive
+    /// the block that constructs them. `dispatch` is what lets `span!`
+    /// build one on the stack and still hand the caller back something
+    /// that isn't tied to that block's lifetime.
+    #[doc(hidden)]
+    pub fn dispatch<'a>(self, metadata: &'a Metadata<'a>, values: &'a field::ValueSet<'a>) -> Span {
+        let new_span = self.finish(metadata, values);
+        ::dispatcher::Dispatch::get_default(|dispatch| dispatch.new_span(&new_span))
+    }
 }
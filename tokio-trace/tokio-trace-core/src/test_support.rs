@@ -0,0 +1,227 @@
+//! Test support for asserting on span construction.
+//!
+//! This module is gated behind the `test` feature (see the `#[cfg(feature
+//! = "test")]` on its `mod test_support;` declaration). It provides a
+//! [`MockSpan`] builder for describing the [`NewSpan`](::span::NewSpan) an
+//! instrumentation point is expected to produce, paired with a
+//! [`MockSubscriber`] that records `new_span` calls and panics with a
+//! descriptive diff the moment an observed `NewSpan` doesn't match the
+//! next queued expectation. This lets downstream instrumentation authors
+//! unit test their span construction against the exact `NewSpan`/`Parent`
+//! shapes defined in [`span`](::span), without standing up a real
+//! `Subscriber`.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+use field::{Field, Visit};
+use span::{NewSpan, Span};
+use {Level, Subscriber};
+
+/// Describes the [`NewSpan`](::span::NewSpan) that a piece of
+/// instrumentation is expected to produce.
+///
+/// Build one with [`MockSpan::new`] and the methods below, then queue it up
+/// with [`MockSubscriber::expect`].
+#[derive(Debug, Default)]
+pub struct MockSpan {
+    name: Option<String>,
+    level: Option<Level>,
+    target: Option<String>,
+    fields: Vec<String>,
+    parent: Option<ExpectedParent>,
+}
+
+#[derive(Debug)]
+enum ExpectedParent {
+    Root,
+    Contextual,
+    Explicit,
+}
+
+impl MockSpan {
+    /// Returns a new, empty expectation that matches any span.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects the span to have the given `name`.
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    /// Expects the span to have been recorded at the given `level`.
+    pub fn at_level(mut self, level: Level) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Expects the span's metadata to report the given `target`.
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_owned());
+        self
+    }
+
+    /// Expects the span's values to include a field named `name`.
+    pub fn with_field(mut self, name: &str) -> Self {
+        self.fields.push(name.to_owned());
+        self
+    }
+
+    /// Expects the span to have an explicitly-specified parent.
+    ///
+    /// A `NewSpan`'s parent is an opaque `Span` ID with no name reachable
+    /// from it, so this can't assert on *which* span is the parent, only
+    /// that one was given explicitly. Pair this with a [`MockSubscriber`]
+    /// expectation on the parent span itself if the parent's identity
+    /// matters.
+    pub fn with_explicit_parent(mut self) -> Self {
+        self.parent = Some(ExpectedParent::Explicit);
+        self
+    }
+
+    /// Expects the span to be a child of the current span.
+    pub fn with_contextual_parent(mut self) -> Self {
+        self.parent = Some(ExpectedParent::Contextual);
+        self
+    }
+
+    /// Expects the span to be a root.
+    pub fn with_root(mut self) -> Self {
+        self.parent = Some(ExpectedParent::Root);
+        self
+    }
+
+    /// Panics with a descriptive diff if `new_span` does not match this
+    /// expectation.
+    fn assert_matches(&self, new_span: &NewSpan) {
+        let metadata = new_span.metadata();
+
+        if let Some(ref name) = self.name {
+            assert_eq!(
+                name.as_str(),
+                metadata.name(),
+                "expected a span named {:?}, but got {:?}",
+                name,
+                metadata.name()
+            );
+        }
+
+        if let Some(level) = self.level {
+            assert_eq!(
+                level,
+                *metadata.level(),
+                "expected a span at level {:?}, but got {:?}",
+                level,
+                metadata.level()
+            );
+        }
+
+        if let Some(ref target) = self.target {
+            assert_eq!(
+                target.as_str(),
+                metadata.target(),
+                "expected a span with target {:?}, but got {:?}",
+                target,
+                metadata.target()
+            );
+        }
+
+        if !self.fields.is_empty() {
+            let mut recorded = RecordedFields::default();
+            new_span.values().record(&mut recorded);
+            for field in &self.fields {
+                assert!(
+                    recorded.0.contains(field),
+                    "expected a span with field {:?}, but only found {:?}",
+                    field,
+                    recorded.0
+                );
+            }
+        }
+
+        match self.parent {
+            Some(ExpectedParent::Root) => assert!(
+                new_span.is_root(),
+                "expected a root span, but it had a parent"
+            ),
+            Some(ExpectedParent::Contextual) => assert!(
+                new_span.is_in_current(),
+                "expected a span with a contextual parent, but it did not have one"
+            ),
+            Some(ExpectedParent::Explicit) => assert!(
+                new_span.parent().is_some(),
+                "expected a span with an explicit parent, but it did not have one"
+            ),
+            None => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct RecordedFields(Vec<String>);
+
+impl Visit for RecordedFields {
+    fn record_debug(&mut self, field: &Field, _value: &fmt::Debug) {
+        self.0.push(field.name().to_owned());
+    }
+}
+
+/// A `Subscriber` that records `new_span` calls and asserts each one
+/// against a queue of [`MockSpan`] expectations.
+///
+/// Panics, with a descriptive diff, if an observed `NewSpan` doesn't match
+/// the next queued expectation, or if the subscriber is dropped with
+/// unmet expectations still queued.
+#[derive(Debug, Default)]
+pub struct MockSubscriber {
+    expected: Mutex<VecDeque<MockSpan>>,
+}
+
+impl MockSubscriber {
+    /// Returns a new `MockSubscriber` with no queued expectations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues up an expectation for the next `new_span` call.
+    pub fn expect(self, span: MockSpan) -> Self {
+        self.expected.lock().unwrap().push_back(span);
+        self
+    }
+}
+
+impl Subscriber for MockSubscriber {
+    fn new_span(&self, new_span: &NewSpan) -> Span {
+        let expected = self
+            .expected
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("got a new_span call with no matching expectation queued");
+        expected.assert_matches(new_span);
+        Span::from_u64(1)
+    }
+}
+
+impl Drop for MockSubscriber {
+    fn drop(&mut self) {
+        // If we're already unwinding from a panic (e.g. `assert_matches`
+        // just failed), the queued-but-unseen expectations left behind by
+        // the mismatch are expected, and asserting here would panic while
+        // already panicking and abort the process -- burying the
+        // descriptive diff this module exists to print. Only enforce the
+        // "no leftover expectations" invariant on the non-panicking path.
+        if std::thread::panicking() {
+            return;
+        }
+        let remaining = self.expected.lock().unwrap();
+        assert!(
+            remaining.is_empty(),
+            "MockSubscriber dropped with {} unmet expectation(s)",
+            remaining.len()
+        );
+    }
+}
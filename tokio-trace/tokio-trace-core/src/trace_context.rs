@@ -0,0 +1,123 @@
+//! Distributed trace propagation.
+//!
+//! A [`Span`](::span::Span) ID is only meaningful within the process that
+//! generated it, so a span created by one service and a span created by
+//! another have no relationship unless something carries that context
+//! across the RPC boundary. `TraceContext` is that something. It follows
+//! the [OpenCensus trace/span/parent model][opencensus], carrying a
+//! 128-bit trace ID shared by every span in one causal chain, the current
+//! 64-bit span ID, and an optional parent span ID, so that it can be
+//! injected into outgoing request headers on the client and reconstructed
+//! on the server.
+//!
+//! [opencensus]: https://opencensus.io/tracing/span/
+//!
+//! Random ID generation here depends on the `rand` crate, which must be
+//! added to this crate's `Cargo.toml` as a dependency; since the rest of
+//! this crate is `core`-only, that dependency should stay scoped to this
+//! module rather than becoming a default requirement for callers who never
+//! touch distributed tracing.
+
+use span::Span;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identifies all the spans causally related to one distributed trace.
+///
+/// Unlike a [`Span`](::span::Span) ID, which is only meaningful within the
+/// process that generated it, a `TraceId` is generated once for the trace
+/// as a whole and carried, unchanged, across every process the trace
+/// passes through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TraceId(u128);
+
+/// A serializable snapshot of a span's position within a distributed trace.
+///
+/// A `TraceContext` promotes the explicit-parent idea behind
+/// [`NewSpan::child_of`](::span::NewSpan::child_of) across process
+/// boundaries: it carries the 128-bit [`TraceId`] shared by every causally
+/// related span, the 64-bit ID of the span the context was captured from,
+/// and the ID of that span's parent, if it had one. A `TraceContext` can be
+/// injected into outgoing request headers on the client and extracted on
+/// the server to root a new span in the same trace tree, even though the
+/// two spans were created in different processes.
+///
+/// With the `serde` feature enabled, `TraceContext` implements `Serialize`
+/// and `Deserialize`, so it can be carried as, e.g., a JSON or binary
+/// header value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TraceContext {
+    trace_id: TraceId,
+    span_id: u64,
+    parent_span_id: Option<u64>,
+}
+
+// ===== impl TraceId =====
+
+impl TraceId {
+    /// Generates a new, random `TraceId`.
+    pub fn new() -> Self {
+        TraceId(::rand::random())
+    }
+
+    /// Returns the trace ID as a `u128`.
+    pub fn into_u128(&self) -> u128 {
+        self.0
+    }
+}
+
+impl Default for TraceId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===== impl TraceContext =====
+
+impl TraceContext {
+    /// Returns a new `TraceContext` for a fresh trace, with a random trace
+    /// ID, a random root span ID, and no parent.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: TraceId::new(),
+            span_id: ::rand::random(),
+            parent_span_id: None,
+        }
+    }
+
+    /// Returns a new `TraceContext` that keeps this context's trace ID but
+    /// is reparented under it: the new context's span ID is freshly
+    /// generated, and its parent span ID is this context's span ID.
+    ///
+    /// A client should call this before injecting a context into an
+    /// outgoing request; a server should call it again after extracting
+    /// the context from an incoming request and before rooting its own
+    /// span under it, so that every hop in the call chain gets its own
+    /// span ID while staying linked into the same trace.
+    pub fn new_child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: ::rand::random(),
+            parent_span_id: Some(self.span_id),
+        }
+    }
+
+    /// Returns the 128-bit ID of the trace this context belongs to.
+    pub fn trace_id(&self) -> TraceId {
+        self.trace_id
+    }
+
+    /// Returns the ID of the span this context was captured from.
+    pub fn span_id(&self) -> Span {
+        Span::from_u64(self.span_id)
+    }
+
+    /// Returns the ID of the parent of the span this context was captured
+    /// from, if it had one.
+    pub fn parent_span_id(&self) -> Option<Span> {
+        self.parent_span_id.map(Span::from_u64)
+    }
+}